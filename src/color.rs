@@ -0,0 +1,130 @@
+use colorsys::{ColorAlpha, Rgb};
+use ini_material_color_utilities_rs::hct::Hct;
+
+fn argb_from_rgb(rgb: &Rgb) -> u32 {
+    let a = (rgb.alpha() * 255.0).round() as u32;
+    let r = rgb.red() as u32;
+    let g = rgb.green() as u32;
+    let b = rgb.blue() as u32;
+    (a << 24) | (r << 16) | (g << 8) | b
+}
+
+fn rgb_from_argb(argb: u32) -> Rgb {
+    let a = ((argb >> 24) & 0xff) as f64 / 255.0;
+    let r = ((argb >> 16) & 0xff) as f64;
+    let g = ((argb >> 8) & 0xff) as f64;
+    let b = (argb & 0xff) as f64;
+    Rgb::new(r, g, b, Some(a))
+}
+
+/// How two colors are composited together in [`RgbExt::blend`].
+///
+/// Mirrors the blend utilities in Zed's color crate: each mode operates per-channel on values
+/// normalized to `0.0..=1.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlendMode {
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    SoftLight,
+}
+
+pub trait RgbExt {
+    /// Brighten the color.
+    ///
+    /// What's the different between this and using `Hsl::lighten`?
+    ///
+    /// Using `Hsl::lighten` causes some slight color deviation towards the hue, I.E if you
+    /// darken (by using a negative) amount a color it will make it more saturated towards the
+    /// color hsl
+    ///
+    /// This function doesn't cause this.'
+    fn brigthen(&mut self, amount: f64);
+
+    /// Linearly interpolate towards `other`, `t` clamped to `0.0..=1.0` (`0.0` is `self`, `1.0`
+    /// is `other`).
+    fn mix(&self, other: &Rgb, t: f64) -> Rgb;
+
+    /// Composite `other` over `self` using the given [`BlendMode`].
+    fn blend(&self, other: &Rgb, mode: BlendMode) -> Rgb;
+
+    /// Set the color's HCT Tone (perceptual lightness, `0.0..=100.0`) while preserving its Hue
+    /// and Chroma, unlike adjusting RGB channels directly.
+    fn set_tone(&mut self, tone: f64);
+
+    /// Adjust the color's HCT Tone by `delta`, clamped to `0.0..=100.0`.
+    fn adjust_tone(&mut self, delta: f64);
+}
+
+impl RgbExt for Rgb {
+    fn brigthen(&mut self, amount: f64) {
+        let (red, green, blue) = (self.red(), self.green(), self.blue());
+        self.set_red(red - (255.0 * -(amount / 100.0)).floor());
+        self.set_green(green - (255.0 * -(amount / 100.0)).floor());
+        self.set_blue(blue - (255.0 * -(amount / 100.0)).floor());
+    }
+
+    fn mix(&self, other: &Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        let mut out = self.clone();
+        out.set_red(lerp(self.red(), other.red()));
+        out.set_green(lerp(self.green(), other.green()));
+        out.set_blue(lerp(self.blue(), other.blue()));
+        out
+    }
+
+    fn blend(&self, other: &Rgb, mode: BlendMode) -> Rgb {
+        let blend_channel = |a: f64, b: f64| -> f64 {
+            let (a, b) = (a / 255.0, b / 255.0);
+            let blended = match mode {
+                BlendMode::Multiply => a * b,
+                BlendMode::Screen => 1.0 - (1.0 - a) * (1.0 - b),
+                BlendMode::Overlay => {
+                    if a < 0.5 {
+                        2.0 * a * b
+                    } else {
+                        1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+                    }
+                }
+                BlendMode::Darken => a.min(b),
+                BlendMode::Lighten => a.max(b),
+                BlendMode::SoftLight => {
+                    if b <= 0.5 {
+                        a - (1.0 - 2.0 * b) * a * (1.0 - a)
+                    } else {
+                        let d = if a <= 0.25 {
+                            ((16.0 * a - 12.0) * a + 4.0) * a
+                        } else {
+                            a.sqrt()
+                        };
+                        a + (2.0 * b - 1.0) * (d - a)
+                    }
+                }
+            };
+            blended.clamp(0.0, 1.0) * 255.0
+        };
+
+        let mut out = self.clone();
+        out.set_red(blend_channel(self.red(), other.red()));
+        out.set_green(blend_channel(self.green(), other.green()));
+        out.set_blue(blend_channel(self.blue(), other.blue()));
+        out
+    }
+
+    fn set_tone(&mut self, tone: f64) {
+        let mut hct = Hct::from_int(argb_from_rgb(self));
+        hct.set_tone(tone.clamp(0.0, 100.0));
+        *self = rgb_from_argb(hct.to_int());
+    }
+
+    fn adjust_tone(&mut self, delta: f64) {
+        let mut hct = Hct::from_int(argb_from_rgb(self));
+        hct.set_tone((hct.get_tone() + delta).clamp(0.0, 100.0));
+        *self = rgb_from_argb(hct.to_int());
+    }
+}
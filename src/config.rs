@@ -0,0 +1,254 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{Context, Result};
+use colorsys::{prelude::*, Hsl, Rgb, SaturationInSpace};
+use serde::Deserialize;
+
+use crate::{
+    color::{BlendMode, RgbExt},
+    SchemeMode,
+};
+
+/// An ordered list of adjustments applied to the generated scheme, read from a `--config` file.
+///
+/// This replaces what used to be hard coded in `main`: see [`Pipeline::default_for`] for the
+/// built-in pipeline used when no config is given.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pipeline {
+    #[serde(default, rename = "adjust")]
+    pub adjustments: Vec<Adjustment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Adjustment {
+    /// The scheme role this adjustment is applied to, e.g. `"surface_bright"`.
+    pub target: String,
+    pub op: Op,
+    /// Meaning depends on `op`: a brighten/lighten/darken/saturate delta, or an alpha value for
+    /// `set_alpha`.
+    #[serde(default)]
+    pub amount: Option<f64>,
+    /// The role to copy/mix/blend from. Used by `op = "copy_from"`, `"tone_from"`, `"mix"` and
+    /// `"blend"`.
+    #[serde(default)]
+    pub copy_from: Option<String>,
+    /// Only used by `op = "blend"`.
+    #[serde(default)]
+    pub blend_mode: Option<BlendMode>,
+    /// Restrict this adjustment to the given modes. `None` (the default) means "every mode".
+    #[serde(default)]
+    pub modes: Option<Vec<SchemeMode>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Op {
+    Brighten,
+    Lighten,
+    Darken,
+    Saturate,
+    SetAlpha,
+    /// Copy `copy_from`, then `Hsl::lighten` it by `amount`.
+    CopyFrom,
+    /// Linearly interpolate towards `copy_from`, `amount` as `t` (`0.0..=1.0`).
+    Mix,
+    /// Composite `copy_from` over `target` using `blend_mode`.
+    Blend,
+    /// Set the HCT Tone to `amount` (`0.0..=100.0`), preserving Hue and Chroma.
+    SetTone,
+    /// Adjust the HCT Tone by `amount`, preserving Hue and Chroma.
+    AdjustTone,
+    /// Copy `copy_from`, then adjust its HCT Tone by `amount`, preserving Hue and Chroma.
+    ToneFrom,
+}
+
+impl Pipeline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config `{}`", path.display()))?;
+        toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse config `{}`", path.display()))
+    }
+
+    /// The built-in pipeline used when `--config` is not passed. It keeps the constant dim that
+    /// was hard coded before `--config` existed, but derives the highest surface containers by
+    /// blending/mixing in `primary` and does the per-mode `surface_dim`/`surface_bright` tweaks
+    /// on the HCT Tone axis, so its output differs from the old hard-coded steps.
+    pub fn default_for(mode: SchemeMode) -> Self {
+        let dim = |target: &str| Adjustment {
+            target: target.to_string(),
+            op: Op::Brighten,
+            amount: Some(-1.0),
+            copy_from: None,
+            blend_mode: None,
+            modes: None,
+        };
+
+        // I feel like material UI backgrounds are always too bright?, maybe its me
+        // I dim everything beforehand by a constant factor before actual modifications
+        let mut adjustments: Vec<Adjustment> = [
+            "surface",
+            "surface_dim",
+            "surface_bright",
+            "surface_container",
+            "surface_container_lowest",
+            "surface_container_low",
+            "surface_container_high",
+            "surface_container_highest",
+            "inverse_surface",
+            "primary",
+            "secondary",
+            "tertiary",
+            "primary_container",
+            "secondary_container",
+            "tertiary_container",
+            "error",
+        ]
+        .into_iter()
+        .map(dim)
+        .collect();
+
+        // Derive the highest-elevation surface containers by bringing primary into the
+        // (already-dimmed) containers themselves: an overlay blend for `_high`, a 6% linear mix
+        // for `_highest`. This way they track the source's hue instead of just getting
+        // lighter/darker.
+        adjustments.push(Adjustment {
+            target: "surface_container_high".to_string(),
+            op: Op::Blend,
+            amount: None,
+            copy_from: Some("primary".to_string()),
+            blend_mode: Some(BlendMode::Overlay),
+            modes: None,
+        });
+        adjustments.push(Adjustment {
+            target: "surface_container_highest".to_string(),
+            op: Op::Mix,
+            amount: Some(0.06),
+            copy_from: Some("primary".to_string()),
+            blend_mode: None,
+            modes: None,
+        });
+
+        match mode {
+            SchemeMode::Dark => {
+                // Make surface_dim actually dim, even on dark colorscheme. Tone-adjusted so the
+                // dim doesn't drift the hue.
+                adjustments.push(Adjustment {
+                    target: "surface_dim".to_string(),
+                    op: Op::AdjustTone,
+                    amount: Some(-4.0),
+                    copy_from: None,
+                    blend_mode: None,
+                    modes: Some(vec![SchemeMode::Dark]),
+                });
+                // And make surface_bright a bit less flaring for dark colorscheme, by deriving
+                // it from surface
+                adjustments.push(Adjustment {
+                    target: "surface_bright".to_string(),
+                    op: Op::ToneFrom,
+                    amount: Some(12.0),
+                    copy_from: Some("surface".to_string()),
+                    blend_mode: None,
+                    modes: Some(vec![SchemeMode::Dark]),
+                });
+            }
+            SchemeMode::Light => {
+                // make surface_bright actually bright for light colorschemes. Tone-adjusted so
+                // the brighten doesn't drift the hue.
+                adjustments.push(Adjustment {
+                    target: "surface_bright".to_string(),
+                    op: Op::AdjustTone,
+                    amount: Some(4.0),
+                    copy_from: None,
+                    blend_mode: None,
+                    modes: Some(vec![SchemeMode::Light]),
+                });
+            }
+            SchemeMode::Amoled => {}
+        }
+
+        Self { adjustments }
+    }
+
+    /// Apply every adjustment whose `modes` filter (if any) includes `mode`, in order.
+    pub fn apply(&self, colors: &mut HashMap<&str, Rgb>, mode: SchemeMode) {
+        for adjustment in &self.adjustments {
+            if let Some(modes) = &adjustment.modes {
+                if !modes.contains(&mode) {
+                    continue;
+                }
+            }
+
+            let Some(&target) = colors.keys().find(|k| **k == adjustment.target.as_str()) else {
+                continue;
+            };
+
+            let mut rgb = colors[target].clone();
+            match adjustment.op {
+                Op::Brighten => rgb.brigthen(adjustment.amount.unwrap_or(0.0)),
+                Op::Lighten => {
+                    let mut hsl: Hsl = rgb.into();
+                    hsl.lighten(adjustment.amount.unwrap_or(0.0));
+                    rgb = hsl.into();
+                }
+                Op::Darken => {
+                    let mut hsl: Hsl = rgb.into();
+                    hsl.lighten(-adjustment.amount.unwrap_or(0.0));
+                    rgb = hsl.into();
+                }
+                Op::Saturate => {
+                    let mut hsl: Hsl = rgb.into();
+                    hsl.saturate(SaturationInSpace::Hsl(adjustment.amount.unwrap_or(0.0)));
+                    rgb = hsl.into();
+                }
+                Op::SetAlpha => rgb.set_alpha(adjustment.amount.unwrap_or(1.0)),
+                Op::SetTone => rgb.set_tone(adjustment.amount.unwrap_or(50.0)),
+                Op::AdjustTone => rgb.adjust_tone(adjustment.amount.unwrap_or(0.0)),
+                Op::CopyFrom => {
+                    let Some(source) = &adjustment.copy_from else {
+                        continue;
+                    };
+                    let Some(&source) = colors.keys().find(|k| **k == source.as_str()) else {
+                        continue;
+                    };
+                    let mut hsl: Hsl = colors[source].clone().into();
+                    hsl.lighten(adjustment.amount.unwrap_or(0.0));
+                    rgb = hsl.into();
+                }
+                Op::ToneFrom => {
+                    let Some(source) = &adjustment.copy_from else {
+                        continue;
+                    };
+                    let Some(&source) = colors.keys().find(|k| **k == source.as_str()) else {
+                        continue;
+                    };
+                    // Tone-adjust rather than `Hsl::lighten` so the derived color tracks the
+                    // source's Hue/Chroma instead of drifting towards it.
+                    let mut derived = colors[source].clone();
+                    derived.adjust_tone(adjustment.amount.unwrap_or(0.0));
+                    rgb = derived;
+                }
+                Op::Mix => {
+                    let Some(source) = &adjustment.copy_from else {
+                        continue;
+                    };
+                    let Some(&source) = colors.keys().find(|k| **k == source.as_str()) else {
+                        continue;
+                    };
+                    rgb = rgb.mix(&colors[source], adjustment.amount.unwrap_or(0.5));
+                }
+                Op::Blend => {
+                    let Some(source) = &adjustment.copy_from else {
+                        continue;
+                    };
+                    let Some(&source) = colors.keys().find(|k| **k == source.as_str()) else {
+                        continue;
+                    };
+                    let mode = adjustment.blend_mode.unwrap_or(BlendMode::Overlay);
+                    rgb = rgb.blend(&colors[source], mode);
+                }
+            }
+            colors.insert(target, rgb);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt::Debug, path::PathBuf};
 
 use clap::{Parser, Subcommand, ValueEnum};
-use colorsys::{prelude::*, ColorAlpha, Hsl, Rgb};
+use colorsys::{ColorAlpha, Rgb};
 use image::{imageops::FilterType, GenericImageView, Rgba};
 use ini_material_color_utilities_rs::{
     palettes::core::{ColorPalette, CorePalette},
@@ -12,27 +12,11 @@ use ini_material_color_utilities_rs::{
 
 use anyhow::{Context, Result};
 
-pub trait RgbExt {
-    /// Brighten the color.
-    ///
-    /// What's the different between this and using `Hsl::lighten`?
-    ///
-    /// Using `Hsl::lighten` causes some slight color deviation towards the hue, I.E if you
-    /// darken (by using a negative) amount a color it will make it more saturated towards the
-    /// color hsl
-    ///
-    /// This function doesn't cause this.'
-    fn brigthen(&mut self, amount: f64);
-}
-
-impl RgbExt for Rgb {
-    fn brigthen(&mut self, amount: f64) {
-        let (red, green, blue) = (self.red(), self.green(), self.blue());
-        self.set_red(red - (255.0 * -(amount / 100.0)).floor());
-        self.set_green(green - (255.0 * -(amount / 100.0)).floor());
-        self.set_blue(blue - (255.0 * -(amount / 100.0)).floor());
-    }
-}
+mod color;
+mod config;
+mod output;
+mod palette_match;
+mod template;
 
 #[derive(Debug, Parser)]
 #[command(version, long_about = None)]
@@ -62,6 +46,46 @@ struct Cli {
         default_value = "default"
     )]
     pub palette: ColorPalette,
+
+    /// A template file to render with the generated scheme. Repeatable; paired positionally
+    /// with `--output`.
+    #[arg(long = "template", value_name = "FILE", global = true)]
+    pub templates: Vec<PathBuf>,
+
+    /// Where to write the corresponding `--template`'s rendered output. Repeatable; must be
+    /// passed the same number of times as `--template`.
+    #[arg(long = "output", value_name = "FILE", global = true)]
+    pub outputs: Vec<PathBuf>,
+
+    /// A post-processing pipeline config (see `config::Pipeline`). When omitted, the crate's
+    /// built-in default profile (`config::Pipeline::default_for`) is used.
+    #[arg(long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Snap every generated color to the closest entry (by CIE76 `ΔE`) of a base16/base24-style
+    /// palette file, or a bare JSON list of hex codes.
+    #[arg(long, value_name = "FILE", global = true)]
+    pub match_palette: Option<PathBuf>,
+
+    /// Encoding used for each color in the output JSON.
+    #[arg(
+        value_enum,
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value = "hex-no-hash"
+    )]
+    pub output_format: output::OutputFormat,
+
+    /// Channel order used when `--output-format packed` is selected.
+    #[arg(
+        value_enum,
+        long,
+        global = true,
+        value_name = "ORDER",
+        default_value = "argb"
+    )]
+    pub channel_order: output::ChannelOrder,
 }
 
 #[derive(Clone, Debug, Subcommand)]
@@ -72,8 +96,9 @@ enum SchemeSource {
     Color { hex: String },
 }
 
-#[derive(Clone, Debug, PartialEq, ValueEnum)]
-enum SchemeMode {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemeMode {
     Amoled,
     Dark,
     Light,
@@ -141,55 +166,26 @@ fn main() -> Result<()> {
         .map(|(k, v)| (k, Rgb::from_hex_str(&v).unwrap()))
         .collect();
 
-    // TODO: Read post-processing actions from a given file instead of hard coding them.
-
-    // I feel like material UI backgrounds are always too bright?, maybe its me
-    // I dim everything beforehand by a constant factor before actual modifications
-    for color in [
-        "surface",
-        "surface_dim",
-        "surface_bright",
-        "surface_container",
-        "surface_container_lowest",
-        "surface_container_low",
-        "surface_container_high",
-        "surface_container_highest",
-        "inverse_surface",
-        "primary",
-        "secondary",
-        "tertiary",
-        "primary_container",
-        "secondary_container",
-        "tertiary_container",
-        "error",
-    ] {
-        let mut rgb = colors[color].clone();
-        rgb.brigthen(-1.0);
-        colors.insert(color, rgb);
-    }
+    let pipeline = match &cli.config {
+        Some(path) => config::Pipeline::load(path)?,
+        None => config::Pipeline::default_for(cli.mode),
+    };
+    pipeline.apply(&mut colors, cli.mode);
 
-    if cli.mode == SchemeMode::Dark {
-        // Make surface_dim actually dim, even on dark colorscheme
-        let mut rgb = colors["surface_dim"].clone();
-        rgb.brigthen(-1.0);
-        colors.insert("surface_dim", rgb);
-        // And make surface_bright a bit less flaring for dark colorscheme
-        // and doing so by deriving it from surface
-        let mut hsl: Hsl = colors["surface"].clone().into();
-        hsl.lighten(1.35);
-        colors.insert("surface_bright", hsl.into());
+    if let Some(path) = &cli.match_palette {
+        let palette = palette_match::load_palette(path)?;
+        palette_match::snap_to_palette(&mut colors, &palette);
     }
-    if cli.mode == SchemeMode::Light {
-        //  make surface_bright actually bright for light colorschemes
-        let mut rgb = colors["surface_bright"].clone();
-        rgb.brigthen(1.0);
-        colors.insert("surface_bright", rgb);
+
+    if !cli.templates.is_empty() {
+        template::render_templates(&colors, &cli.templates, &cli.outputs)?;
+        return Ok(());
     }
 
     // Now serialize back and you are done
     let colors: HashMap<&str, String> = colors
-        .into_iter()
-        .map(|(k, v)| (k, v.to_hex_string().replace("#", "")))
+        .iter()
+        .map(|(k, v)| (*k, output::encode(v, cli.output_format, cli.channel_order)))
         .collect();
     let json_str = serde_json::to_string(&colors).unwrap();
 
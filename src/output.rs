@@ -0,0 +1,59 @@
+use clap::ValueEnum;
+use colorsys::{ColorAlpha, Hsl, Rgb};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    Hex,
+    HexNoHash,
+    Rgb,
+    Rgba,
+    Hsl,
+    /// A `0x`-prefixed packed 32-bit integer, channel order given by `--channel-order`.
+    Packed,
+}
+
+/// Channel order for [`OutputFormat::Packed`], mirroring `palette`'s `Packed`/`into_u32`
+/// approach of shifting each 8-bit channel into place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ChannelOrder {
+    Argb,
+    Abgr,
+    Rgba,
+    Bgra,
+}
+
+fn pack(order: ChannelOrder, a: u8, r: u8, g: u8, b: u8) -> u32 {
+    let (c0, c1, c2, c3) = match order {
+        ChannelOrder::Argb => (a, r, g, b),
+        ChannelOrder::Abgr => (a, b, g, r),
+        ChannelOrder::Rgba => (r, g, b, a),
+        ChannelOrder::Bgra => (b, g, r, a),
+    };
+    ((c0 as u32) << 24) | ((c1 as u32) << 16) | ((c2 as u32) << 8) | (c3 as u32)
+}
+
+/// Encode `rgb` as `format`, consulting `order` only for [`OutputFormat::Packed`].
+pub fn encode(rgb: &Rgb, format: OutputFormat, order: ChannelOrder) -> String {
+    let (r, g, b) = (rgb.red() as u8, rgb.green() as u8, rgb.blue() as u8);
+
+    match format {
+        OutputFormat::Hex => format!("#{r:02x}{g:02x}{b:02x}"),
+        OutputFormat::HexNoHash => format!("{r:02x}{g:02x}{b:02x}"),
+        OutputFormat::Rgb => format!("rgb({r}, {g}, {b})"),
+        OutputFormat::Rgba => format!("rgba({r}, {g}, {b}, {:.2})", rgb.alpha()),
+        OutputFormat::Hsl => {
+            let hsl: Hsl = rgb.clone().into();
+            format!(
+                "hsl({:.0}, {:.0}%, {:.0}%)",
+                hsl.hue(),
+                hsl.saturation(),
+                hsl.lightness()
+            )
+        }
+        OutputFormat::Packed => {
+            let a = (rgb.alpha() * 255.0).round() as u8;
+            format!("0x{:08x}", pack(order, a, r, g, b))
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{bail, Context, Result};
+use colorsys::Rgb;
+use serde::Deserialize;
+
+/// A palette file is either a base16/base24-style map of names to hex codes, or a bare list of
+/// hex codes. Either way only the colors themselves matter for matching.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum PaletteFile {
+    Named(HashMap<String, String>),
+    List(Vec<String>),
+}
+
+/// Whether `key` is a base16 (`base00`..`base0F`) or base24 (`base10`..`base17`) color slot, as
+/// opposed to metadata keys like `"scheme"`/`"author"` that real base16/base24 files carry
+/// alongside their colors.
+fn is_palette_slot_key(key: &str) -> bool {
+    key.strip_prefix("base").is_some_and(|suffix| {
+        suffix.len() == 2 && u8::from_str_radix(suffix, 16).is_ok_and(|slot| slot <= 0x17)
+    })
+}
+
+pub fn load_palette(path: &Path) -> Result<Vec<Rgb>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read palette `{}`", path.display()))?;
+    let file: PaletteFile = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse palette `{}`", path.display()))?;
+
+    let hexes = match file {
+        PaletteFile::Named(map) => map
+            .into_iter()
+            .filter(|(key, _)| is_palette_slot_key(key))
+            .map(|(_, hex)| hex)
+            .collect(),
+        PaletteFile::List(list) => list,
+    };
+
+    if hexes.is_empty() {
+        bail!("Palette `{}` contains no colors", path.display());
+    }
+
+    hexes
+        .iter()
+        .map(|hex| Rgb::from_hex_str(hex).with_context(|| format!("Malformed hex code `{hex}`")))
+        .collect()
+}
+
+/// CIE L*a*b* (D65 white point), used so "closest color" tracks human perception rather than
+/// raw RGB distance.
+fn rgb_to_lab(rgb: &Rgb) -> (f64, f64, f64) {
+    fn linearize(channel: f64) -> f64 {
+        let c = channel / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    let (r, g, b) = (
+        linearize(rgb.red()),
+        linearize(rgb.green()),
+        linearize(rgb.blue()),
+    );
+    let x = r * 0.4124564 + g * 0.3575761 + b * 0.1804375;
+    let y = r * 0.2126729 + g * 0.7151522 + b * 0.0721750;
+    let z = r * 0.0193339 + g * 0.1191920 + b * 0.9503041;
+
+    const WHITE: (f64, f64, f64) = (0.95047, 1.0, 1.08883);
+    fn f(t: f64) -> f64 {
+        const DELTA: f64 = 6.0 / 29.0;
+        if t > DELTA.powi(3) {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA.powi(2)) + 4.0 / 29.0
+        }
+    }
+
+    let (fx, fy, fz) = (f(x / WHITE.0), f(y / WHITE.1), f(z / WHITE.2));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// CIE76 `ΔE`: Euclidean distance in L*a*b* space.
+fn delta_e_76(a: &Rgb, b: &Rgb) -> f64 {
+    let (l1, a1, b1) = rgb_to_lab(a);
+    let (l2, a2, b2) = rgb_to_lab(b);
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+}
+
+fn nearest<'a>(color: &Rgb, palette: &'a [Rgb]) -> &'a Rgb {
+    palette
+        .iter()
+        .min_by(|a, b| {
+            delta_e_76(color, a)
+                .partial_cmp(&delta_e_76(color, b))
+                .unwrap()
+        })
+        .expect("palette is non-empty")
+}
+
+/// Snap every color in `colors` to the closest entry in `palette`.
+pub fn snap_to_palette(colors: &mut HashMap<&str, Rgb>, palette: &[Rgb]) {
+    for rgb in colors.values_mut() {
+        *rgb = nearest(rgb, palette).clone();
+    }
+}
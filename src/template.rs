@@ -0,0 +1,78 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colorsys::Rgb;
+use serde::Serialize;
+use upon::Engine;
+
+/// A single scheme color, exposed to templates with a handful of common encodings so templates
+/// don't need to reimplement hex/rgb parsing themselves.
+#[derive(Debug, Clone, Serialize)]
+struct TemplateColor {
+    /// `#rrggbb`
+    hex: String,
+    /// `rrggbb`, with no leading `#` (handy for configs that add their own prefix).
+    strip: String,
+    /// `rgb(r, g, b)`
+    rgb: String,
+}
+
+impl TemplateColor {
+    fn from_rgb(rgb: &Rgb) -> Self {
+        let (r, g, b) = (rgb.red() as u8, rgb.green() as u8, rgb.blue() as u8);
+        Self {
+            hex: format!("#{r:02x}{g:02x}{b:02x}"),
+            strip: format!("{r:02x}{g:02x}{b:02x}"),
+            rgb: format!("rgb({r}, {g}, {b})"),
+        }
+    }
+}
+
+/// `{{ primary.hex | set_alpha: 0.5 }}` -> `rgba(r, g, b, 0.5)`
+fn set_alpha(value: &str, alpha: f64) -> Result<String, String> {
+    let rgb = Rgb::from_hex_str(value).map_err(|err| err.to_string())?;
+    Ok(format!(
+        "rgba({}, {}, {}, {alpha})",
+        rgb.red() as u8,
+        rgb.green() as u8,
+        rgb.blue() as u8,
+    ))
+}
+
+/// Render every `(template, output)` pair using the final scheme `colors`, substituting
+/// `{{ <role>.hex }}`, `{{ <role>.rgb }}` and `{{ <role>.strip }}` for each role name.
+///
+/// `templates` and `outputs` must be the same length; they're paired up positionally, i.e. the
+/// Nth `--template` is rendered into the Nth `--output`.
+pub fn render_templates(
+    colors: &HashMap<&str, Rgb>,
+    templates: &[PathBuf],
+    outputs: &[PathBuf],
+) -> Result<()> {
+    if templates.len() != outputs.len() {
+        bail!("--template and --output must be passed the same number of times");
+    }
+
+    let mut engine = Engine::new();
+    engine.add_filter("set_alpha", set_alpha);
+
+    let data: HashMap<&str, TemplateColor> = colors
+        .iter()
+        .map(|(name, rgb)| (*name, TemplateColor::from_rgb(rgb)))
+        .collect();
+
+    for (template_path, output_path) in templates.iter().zip(outputs) {
+        let source = std::fs::read_to_string(template_path)
+            .with_context(|| format!("Failed to read template `{}`", template_path.display()))?;
+        let rendered = engine
+            .compile(&source)
+            .with_context(|| format!("Failed to parse template `{}`", template_path.display()))?
+            .render(&engine, &data)
+            .to_string()
+            .with_context(|| format!("Failed to render template `{}`", template_path.display()))?;
+        std::fs::write(output_path, rendered)
+            .with_context(|| format!("Failed to write `{}`", output_path.display()))?;
+    }
+
+    Ok(())
+}